@@ -41,22 +41,71 @@
 
 #![no_std]
 
+#[cfg(feature = "io")]
+extern crate core_io;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// Determines whether `byte` is the leading code unit of a UTF-8 sequence,
+// i.e. not a continuation byte. Used to skip partially overwritten code unit
+// sequences at the start of the logical log content.
+fn is_utf8_leader(byte: u8) -> bool {
+    byte & 0b10000000 == 0b00000000 ||
+    byte & 0b11100000 == 0b11000000 ||
+    byte & 0b11110000 == 0b11100000 ||
+    byte & 0b11111000 == 0b11110000
+}
+
 /// A ring buffer that stores UTF-8 text.
 ///
 /// Anything that implements `AsMut<[u8]>` can be used for backing storage;
 /// e.g. `[u8; N]`, `Vec<[u8]>`, `Box<[u8]>`.
-#[derive(Debug)]
+///
+/// The backing storage and the write position are kept behind an
+/// [`UnsafeCell`]/[`AtomicUsize`] pair rather than plain fields, so that a
+/// buffer can be unsafely [split](#method.split_ref) into a write half and a
+/// read half. This costs nothing observable for the common single-owner use
+/// case.
 pub struct LogBuffer<T: AsRef<[u8]> + AsMut<[u8]>> {
-    buffer:   T,
-    position: usize
+    buffer:        UnsafeCell<T>,
+    position:      AtomicUsize,
+    total_written: AtomicUsize
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]> + fmt::Debug> fmt::Debug for LogBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogBuffer")
+            .field("buffer", &self.buffer())
+            .field("position", &self.position.load(Ordering::Relaxed))
+            .field("overwritten", &self.overwritten())
+            .finish()
+    }
 }
 
+// Safety: this only asserts that a `&LogBuffer<T>` may be *observed* from
+// another execution context without itself being unsound at the type level
+// (there is no safe way to obtain a `&mut T` from a shared `&LogBuffer<T>`).
+// It does NOT mean concurrent use of a `&LogBuffer<T>` is race-free: actually
+// reading and writing the buffer concurrently (as `split_ref` enables) is
+// sound only under the contract documented there, which this impl exists to
+// make possible to express in the first place.
+unsafe impl<T: AsRef<[u8]> + AsMut<[u8]> + Send> Sync for LogBuffer<T> {}
+
 impl<T: AsRef<[u8]> + AsMut<[u8]>> LogBuffer<T> {
     /// Creates a new ring buffer, backed by `storage`.
     ///
     /// The buffer is cleared after creation.
     pub fn new(storage: T) -> LogBuffer<T> {
-        let mut buffer = LogBuffer { buffer: storage, position: 0 };
+        let mut buffer = LogBuffer {
+            buffer:        UnsafeCell::new(storage),
+            position:      AtomicUsize::new(0),
+            total_written: AtomicUsize::new(0)
+        };
         buffer.clear();
         buffer
     }
@@ -67,7 +116,25 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> LogBuffer<T> {
     /// The `clear()` method should be called before use.
     /// However, this function can be used in a static initializer.
     pub const fn uninitialized(storage: T) -> LogBuffer<T> {
-        LogBuffer { buffer: storage, position: 0 }
+        LogBuffer {
+            buffer:        UnsafeCell::new(storage),
+            position:      AtomicUsize::new(0),
+            total_written: AtomicUsize::new(0)
+        }
+    }
+
+    // Safety: the caller must have exclusive access to the buffer contents,
+    // either via `&mut self` or the single-producer/single-consumer contract
+    // documented on `split_ref`.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn buffer_mut(&self) -> &mut [u8] {
+        unsafe { (*self.buffer.get()).as_mut() }
+    }
+
+    fn buffer(&self) -> &[u8] {
+        // Safety: shared access to the backing storage is always allowed;
+        // only taking a `&mut [u8]` out of the `UnsafeCell` requires care.
+        unsafe { (*self.buffer.get()).as_ref() }
     }
 
     /// Clears the buffer.
@@ -76,8 +143,10 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> LogBuffer<T> {
     ///
     /// This function takes O(n) time where n is buffer length.
     pub fn clear(&mut self) {
-        self.position = 0;
-        for b in self.buffer.as_mut().iter_mut() {
+        self.position.store(0, Ordering::Relaxed);
+        self.total_written.store(0, Ordering::Relaxed);
+        // Safety: `&mut self` guarantees exclusive access.
+        for b in unsafe { self.buffer_mut() }.iter_mut() {
             // Any non-leading UTF-8 code unit would do, but 0xff looks like an obvious sentinel.
             // Can't be 0x00 since that is a valid codepoint.
             *b = 0xff;
@@ -88,41 +157,130 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> LogBuffer<T> {
     ///
     /// This function takes O(1) time.
     pub fn is_empty(&self) -> bool {
-        let buffer = self.buffer.as_ref();
-        self.position == 0 &&
+        let buffer = self.buffer();
+        self.position.load(Ordering::Relaxed) == 0 &&
             (buffer.len() == 0 || buffer[buffer.len() - 1] == 0xff)
     }
 
-    fn rotate(&mut self) {
-        self.buffer.as_mut().rotate_left(self.position);
-        self.position = 0;
+    /// Returns the number of bytes that have been dropped, i.e. written but
+    /// no longer held because newer bytes overwrote them, since the buffer
+    /// was last cleared. Extracting the contents does not reset this count;
+    /// only [`clear`](#method.clear) does.
+    ///
+    /// This mirrors how kernel ring buffers such as `dmesg` report lost
+    /// messages: a consumer can check this before [`extract`](#method.extract)
+    /// and emit a "N bytes dropped" marker if the buffer has cycled.
+    ///
+    /// This function takes O(1) time.
+    pub fn overwritten(&self) -> usize {
+        self.total_written.load(Ordering::Relaxed).saturating_sub(self.buffer().len())
+    }
+
+    fn rotate(&self) {
+        let position = self.position.load(Ordering::Relaxed);
+        // Safety: see the contract note on `buffer_mut`.
+        unsafe { self.buffer_mut() }.rotate_left(position);
+        self.position.store(0, Ordering::Relaxed);
+    }
+
+    fn as_slices_impl(&self) -> (&str, &str) {
+        let position = self.position.load(Ordering::Relaxed);
+        let (head, tail) = self.buffer().split_at(position);
+
+        for i in 0..tail.len() {
+            if is_utf8_leader(tail[i]) {
+                return (core::str::from_utf8(&tail[i..]).unwrap(),
+                        core::str::from_utf8(head).unwrap())
+            }
+        }
+        for i in 0..head.len() {
+            if is_utf8_leader(head[i]) {
+                return ("", core::str::from_utf8(&head[i..]).unwrap())
+            }
+        }
+        ("", "")
+    }
+
+    fn extract_impl(&self) -> &str {
+        self.rotate();
+        self.as_slices_impl().0
+    }
+
+    fn extract_lines_impl(&self) -> core::str::Lines<'_> {
+        self.rotate();
+
+        let slice = self.buffer();
+        for i in 0..slice.len() {
+            if i > 0 && slice[i - 1] == b'\n' {
+                return core::str::from_utf8(&slice[i..]).unwrap().lines()
+            }
+        }
+        return "".lines()
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        // Safety: see the contract note on `buffer_mut`.
+        let buffer = unsafe { self.buffer_mut() };
+        let cap = buffer.len();
+
+        // `overwritten()` derives its result from this cheap running total, rather
+        // than tracking the overwritten count directly.
+        self.total_written.fetch_add(bytes.len(), Ordering::Relaxed);
+
+        if cap == 0 {
+            // Nothing can ever be stored in a zero-capacity buffer; return
+            // before any of the ring arithmetic below, which divides by `cap`.
+            return
+        }
+
+        let mut bytes = bytes;
+        // Only the last `cap` bytes of `bytes` can survive in the ring; anything
+        // before that is guaranteed to be overwritten before it is ever read.
+        if bytes.len() > cap {
+            bytes = &bytes[bytes.len() - cap..];
+        }
+
+        let position = self.position.load(Ordering::Relaxed);
+        let tail = cap - position;
+        if bytes.len() <= tail {
+            buffer[position..position + bytes.len()].copy_from_slice(bytes);
+            self.position.store((position + bytes.len()) % cap, Ordering::Relaxed);
+        } else {
+            let (head, wrapped) = bytes.split_at(tail);
+            buffer[position..cap].copy_from_slice(head);
+            buffer[..wrapped.len()].copy_from_slice(wrapped);
+            self.position.store(wrapped.len(), Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the contents of the ring buffer as two string slices, excluding
+    /// any partially overwritten UTF-8 code unit sequences at the beginning.
+    ///
+    /// The first slice is the logical start of the log, running up to the end
+    /// of the backing storage; the second slice is the part that wrapped
+    /// around to the beginning of the backing storage and logically follows
+    /// the first. Either slice, or both, may be empty.
+    ///
+    /// Unlike [`extract`](#method.extract), this function does not rotate or
+    /// otherwise mutate the ring buffer, so it can be called as often as
+    /// needed without incurring the O(n) cost of a rotation.
+    ///
+    /// This function takes O(n) time where n is buffer length.
+    pub fn as_slices(&self) -> (&str, &str) {
+        self.as_slices_impl()
     }
 
     /// Extracts the contents of the ring buffer as a string slice, excluding any
     /// partially overwritten UTF-8 code unit sequences at the beginning.
     ///
     /// Extraction rotates the contents of the ring buffer such that all of its
-    /// contents becomes contiguous in memory.
+    /// contents becomes contiguous in memory. If the content should only be
+    /// read, not rotated into place, use [`as_slices`](#method.as_slices)
+    /// instead.
     ///
     /// This function takes O(n) time where n is buffer length.
     pub fn extract(&mut self) -> &str {
-        self.rotate();
-
-        // Skip any non-leading UTF-8 code units at the start.
-        fn is_utf8_leader(byte: u8) -> bool {
-            byte & 0b10000000 == 0b00000000 ||
-            byte & 0b11100000 == 0b11000000 ||
-            byte & 0b11110000 == 0b11100000 ||
-            byte & 0b11111000 == 0b11110000
-        }
-
-        let buffer = self.buffer.as_mut();
-        for i in 0..buffer.len() {
-            if is_utf8_leader(buffer[i]) {
-                return core::str::from_utf8(&buffer[i..]).unwrap()
-            }
-        }
-        return ""
+        self.extract_impl()
     }
 
     /// Extracts the contents of the ring buffer as an iterator over its lines,
@@ -136,16 +294,40 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> LogBuffer<T> {
     ///
     /// This function takes O(n) time where n is buffer length.
     pub fn extract_lines(&mut self) -> core::str::Lines {
-        self.rotate();
+        self.extract_lines_impl()
+    }
 
-        let buffer = self.buffer.as_mut();
-        for i in 0..buffer.len() {
-            if i > 0 && buffer[i - 1] == b'\n' {
-                let slice = core::str::from_utf8(&buffer[i..]).unwrap();
-                return slice.lines()
-            }
-        }
-        return "".lines()
+    /// Splits the buffer into a write-only [`Producer`] and a read-only
+    /// [`Consumer`], borrowing `self`.
+    ///
+    /// This lets a write side and a read side be handed to different
+    /// execution contexts — for example, an interrupt handler can own the
+    /// `Producer` and append log lines, while the main loop owns the
+    /// `Consumer` and drains them.
+    ///
+    /// # Safety
+    ///
+    /// `Producer` and `Consumer` coordinate only through a plain atomic write
+    /// position; that index is bookkeeping, not a synchronization mechanism.
+    /// Actually reading the buffer's bytes (in any `Consumer` method,
+    /// including [`Consumer::as_slices`]) while the `Producer` may be
+    /// concurrently writing them is a data race, and the compiler cannot
+    /// check that this never happens. The caller must guarantee one of:
+    ///
+    /// - `Producer` and `Consumer` are never used at the same time without
+    ///   external synchronization (e.g. the `Producer` only runs with
+    ///   interrupts disabled, or both are driven from a single thread that
+    ///   interleaves calls to each one at a time, never concurrently); or
+    /// - some other mechanism (a critical section, a spinlock guarding both
+    ///   halves, turn-taking) makes the two halves' buffer accesses mutually
+    ///   exclusive.
+    ///
+    /// At most one `Producer`/`Consumer` pair may exist for a given buffer at
+    /// a time; the `&mut self` borrow taken here already enforces that, as
+    /// long as `split_ref` is not called again before the previous pair is
+    /// dropped.
+    pub unsafe fn split_ref(&mut self) -> (Producer<'_, T>, Consumer<'_, T>) {
+        (Producer { buffer: self }, Consumer { buffer: self })
     }
 }
 
@@ -154,10 +336,96 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> core::fmt::Write for LogBuffer<T> {
     ///
     /// This function takes O(n) time where n is length of `s`.
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
-        for &b in s.as_bytes() {
-            self.buffer.as_mut()[self.position] = b;
-            self.position = (self.position + 1) % self.buffer.as_mut().len()
-        }
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "io")]
+impl<T: AsRef<[u8]> + AsMut<[u8]>> core_io::Write for LogBuffer<T> {
+    /// Appends raw bytes to the ring buffer.
+    ///
+    /// The bytes are stored as-is. On readout, `extract` skips non-leading
+    /// code units at the start of the contents, so UTF-8 text written in
+    /// pieces split at any byte, or partly overwritten at its start, reads
+    /// out cleanly. Bytes that are not UTF-8 otherwise make readout panic.
+    ///
+    /// This function takes O(n) time where n is length of `buf`.
+    fn write(&mut self, buf: &[u8]) -> core_io::Result<usize> {
+        self.write_bytes(buf);
+        Ok(buf.len())
+    }
+
+    /// This is a no-op; the ring buffer has nothing to flush.
+    fn flush(&mut self) -> core_io::Result<()> {
         Ok(())
     }
 }
+
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]> + AsMut<[u8]>> std::io::Write for LogBuffer<T> {
+    /// Appends raw bytes to the ring buffer.
+    ///
+    /// See the `core_io::Write` impl for details on how this interacts with
+    /// UTF-8 readout.
+    ///
+    /// This function takes O(n) time where n is length of `buf`.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_bytes(buf);
+        Ok(buf.len())
+    }
+
+    /// This is a no-op; the ring buffer has nothing to flush.
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The write half of a [`LogBuffer`] split with [`LogBuffer::split_ref`].
+///
+/// `Producer` implements [`core::fmt::Write`], so it can be used with
+/// `write!` exactly like a whole `LogBuffer`. See [`LogBuffer::split_ref`]
+/// for the safety contract it must be used under.
+pub struct Producer<'a, T: AsRef<[u8]> + AsMut<[u8]> + 'a> {
+    buffer: &'a LogBuffer<T>
+}
+
+impl<'a, T: AsRef<[u8]> + AsMut<[u8]>> core::fmt::Write for Producer<'a, T> {
+    /// Append `s` to the ring buffer.
+    ///
+    /// This function takes O(n) time where n is length of `s`.
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.buffer.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// The read half of a [`LogBuffer`] split with [`LogBuffer::split_ref`].
+///
+/// See [`LogBuffer::split_ref`] for the safety contract it must be used
+/// under.
+pub struct Consumer<'a, T: AsRef<[u8]> + AsMut<[u8]> + 'a> {
+    buffer: &'a LogBuffer<T>
+}
+
+impl<'a, T: AsRef<[u8]> + AsMut<[u8]>> Consumer<'a, T> {
+    /// See [`LogBuffer::as_slices`].
+    pub fn as_slices(&self) -> (&str, &str) {
+        self.buffer.as_slices_impl()
+    }
+
+    /// See [`LogBuffer::extract`].
+    pub fn extract(&mut self) -> &str {
+        self.buffer.extract_impl()
+    }
+
+    /// See [`LogBuffer::extract_lines`].
+    pub fn extract_lines(&mut self) -> core::str::Lines<'_> {
+        self.buffer.extract_lines_impl()
+    }
+
+    /// See [`LogBuffer::overwritten`].
+    pub fn overwritten(&self) -> usize {
+        self.buffer.overwritten()
+    }
+}
@@ -1,5 +1,8 @@
 extern crate log_buffer;
 
+#[cfg(feature = "io")]
+extern crate core_io;
+
 use std::fmt::Write;
 use log_buffer::LogBuffer;
 
@@ -59,6 +62,77 @@ fn cut_off_utf8() {
     assert_eq!(buffer.extract(), "ğŸ˜ŠğŸ˜ŠğŸ˜Ša");
 }
 
+#[test]
+fn bulk_write() {
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    let long = "abcdefghijklmnopqrstuvwxyz0123456789";
+    write!(buffer, "{}", long).unwrap();
+    assert_eq!(buffer.extract(), &long[long.len() - 16..]);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut storage: [u8; 0] = [];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    assert_eq!(buffer.is_empty(), true);
+    write!(buffer, "").unwrap();
+    assert_eq!(buffer.extract(), "");
+}
+
+#[test]
+fn as_slices() {
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    write!(buffer, "foobar").unwrap();
+    assert_eq!(buffer.as_slices(), ("", "foobar"));
+
+    write!(buffer, "verylongthing").unwrap();
+    assert_eq!(buffer.as_slices(), ("barverylongth", "ing"));
+
+    // as_slices() must not have rotated or otherwise mutated the buffer.
+    assert_eq!(buffer.extract(), "barverylongthing");
+}
+
+#[test]
+fn overwritten() {
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    assert_eq!(buffer.overwritten(), 0);
+
+    write!(buffer, "foobar").unwrap();
+    assert_eq!(buffer.overwritten(), 0);
+
+    write!(buffer, "verylongthing").unwrap();
+    assert_eq!(buffer.overwritten(), 3);
+
+    buffer.clear();
+    assert_eq!(buffer.overwritten(), 0);
+}
+
+#[test]
+fn split() {
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+    // Safety: this test drives `producer` and `consumer` from a single
+    // thread, one call at a time, so their accesses never overlap.
+    let (mut producer, mut consumer) = unsafe { buffer.split_ref() };
+
+    write!(producer, "foo").unwrap();
+    assert_eq!(consumer.as_slices(), ("", "foo"));
+
+    write!(producer, "bar").unwrap();
+    assert_eq!(consumer.extract(), "foobar");
+
+    write!(producer, "verylongthing").unwrap();
+    assert_eq!(consumer.as_slices(), ("bar", "verylongthing"));
+    assert_eq!(consumer.extract(), "barverylongthing");
+}
+
 #[test]
 fn lines() {
     let mut storage = [0; 16];
@@ -75,3 +149,35 @@ fn lines() {
     assert_eq!(buffer.extract_lines().collect::<Vec<_>>(),
                vec!["2,fuga", "3,piyo"]);
 }
+
+#[cfg(feature = "io")]
+#[test]
+fn core_io_write() {
+    use core_io::Write as IoWrite;
+
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    buffer.write_all(b"foo").unwrap();
+    buffer.flush().unwrap();
+    assert_eq!(buffer.extract(), "foo");
+
+    buffer.write_all(b"verylongthing").unwrap();
+    assert_eq!(buffer.extract(), "fooverylongthing");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_write() {
+    use std::io::Write as IoWrite;
+
+    let mut storage = [0; 16];
+    let mut buffer = LogBuffer::new(&mut storage);
+
+    buffer.write_all(b"foo").unwrap();
+    buffer.flush().unwrap();
+    assert_eq!(buffer.extract(), "foo");
+
+    buffer.write_all(b"verylongthing").unwrap();
+    assert_eq!(buffer.extract(), "fooverylongthing");
+}